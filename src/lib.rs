@@ -1,24 +1,49 @@
 #![no_std]
 
 use byteorder::{BigEndian, ByteOrder, LittleEndian};
+use bytes::Buf;
 use heapless::{Deque, Vec};
-use rand_distr::{Distribution, Normal};
-
-/// A structure to maintain and calculate rolling statistics (mean, standard deviation) over a fixed-size window of integer samples.
+use rand_core::RngCore;
+use rand_distr::{Cauchy, Distribution, Normal, Poisson};
+
+/// The maximum number of bytes a single LEB128-encoded sample may span. This is enough to hold
+/// a fully encoded 64-bit value (`ceil(64 / 7) == 10`); anything longer is malformed/overlong.
+const MAX_LEB128_BYTES: usize = 10;
+
+/// A structure to maintain and calculate rolling statistics (mean, standard deviation) over a
+/// fixed-capacity window of integer samples.
+///
+/// `N` is the window's maximum capacity, fixed at compile time; `window_size` is the runtime
+/// window size actually in effect and must be between 1 and `N`. `N` defaults to 10 so existing
+/// callers that don't name it keep their previous behavior.
 #[derive(Debug)]
-pub struct RollingStats {
+pub struct RollingStats<const N: usize = 10> {
     /// The size of the rolling window.
     window_size: usize,
     /// The endianness for interpreting bytes.
     endianness: Endianness,
+    /// The byte width of each decoded sample.
+    sample_width: SampleWidth,
+    /// The encoding used to decode samples from a byte stream.
+    encoding: Encoding,
+    /// The probability distribution `sample`/`sample_with` draw from.
+    distribution: SampleDistribution,
     /// A queue of values in the rolling window.
-    values: Deque<i32, 10>,
+    values: Deque<i64, N>,
     /// A buffer for leftover bytes when reading from a stream.
-    remainder: Vec<u8, 4>,
-    /// The sum of values in the window.
-    sum: i64,
-    /// The sum of squares of values in the window.
-    sum_of_squares: i64,
+    remainder: Vec<u8, MAX_LEB128_BYTES>,
+    /// Set once an in-progress LEB128 sequence overflows `remainder`. While set, bytes are
+    /// discarded without decoding until a terminator (high bit clear) is seen, so the stream
+    /// resynchronizes on the next real value instead of decoding leftover continuation bytes
+    /// of the malformed sequence as a fresh one.
+    leb128_resyncing: bool,
+    /// The running mean of the values in the window (Welford's algorithm), tracked in `f64`
+    /// so large-magnitude samples (e.g. epoch-nanosecond `i64` timestamps) don't collapse the
+    /// accumulator's precision; narrowed to `f32` only at the `mean()`/`std_dev()` API boundary.
+    mean: f64,
+    /// The running sum of squared deviations from the mean (Welford's M2), tracked in `f64`
+    /// for the same reason as `mean`.
+    m2: f64,
 }
 
 /// An enum representing the byte order for interpreting byte streams.
@@ -31,38 +56,109 @@ pub enum Endianness {
     Big,
 }
 
-impl RollingStats {
-    /// Creates a new `RollingStats` instance with the given window size and endianness.
+/// An enum representing the byte width of each sample decoded from a byte stream.
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub enum SampleWidth {
+    /// 1-byte (`i8`) samples.
+    One,
+    /// 2-byte (`i16`) samples.
+    Two,
+    /// 4-byte (`i32`) samples.
+    #[default]
+    Four,
+    /// 8-byte (`i64`) samples.
+    Eight,
+}
+
+impl SampleWidth {
+    /// Returns the number of bytes a sample of this width occupies.
+    fn byte_len(self) -> usize {
+        match self {
+            SampleWidth::One => 1,
+            SampleWidth::Two => 2,
+            SampleWidth::Four => 4,
+            SampleWidth::Eight => 8,
+        }
+    }
+}
+
+/// An enum representing how `write` decodes samples from a byte stream.
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub enum Encoding {
+    /// Fixed-width integers, per the configured [`SampleWidth`].
+    #[default]
+    FixedWidth,
+    /// LEB128 variable-length integers: a run of bytes with the high bit set as a
+    /// continuation flag, terminated by a byte with the high bit clear.
+    Leb128,
+}
+
+/// An enum representing the probability distribution `sample`/`sample_with` draw from,
+/// parameterized by the current rolling `mean`/`std_dev`.
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub enum SampleDistribution {
+    /// Normal (Gaussian) distribution: `mean` and `std_dev`.
+    #[default]
+    Normal,
+    /// Cauchy distribution: location = `mean`, scale = `std_dev`. Models heavy-tailed noise.
+    Cauchy,
+    /// Poisson distribution: lambda = `mean`. Intended for non-negative, integer-valued streams.
+    Poisson,
+}
+
+impl<const N: usize> RollingStats<N> {
+    /// Creates a new `RollingStats` instance with the given window size, endianness, sample
+    /// width, encoding and sampling distribution.
     ///
     /// # Arguments
     ///
-    /// * `window_size` - The size of the rolling window (must be between 1 and 10).
+    /// * `window_size` - The size of the rolling window (must be between 1 and the window's
+    ///   compile-time capacity `N`).
     /// * `endianness` - The endianness for interpreting bytes.
+    /// * `sample_width` - The byte width of each sample decoded by [`write`](Self::write)
+    ///   when `encoding` is [`Encoding::FixedWidth`]; ignored for [`Encoding::Leb128`].
+    /// * `encoding` - How `write` decodes samples from the byte stream.
+    /// * `distribution` - The probability distribution [`sample`](Self::sample) and
+    ///   [`sample_with`](Self::sample_with) draw from.
     ///
     /// # Panics
     ///
-    /// Panics if the `window_size` is not between 1 and 10.
+    /// Panics if the `window_size` is not between 1 and `N`.
     ///
     /// # Example
     ///
     /// ```
-    /// use rolling_stats::{RollingStats, Endianness};
+    /// use rolling_stats::{RollingStats, Endianness, SampleWidth, Encoding, SampleDistribution};
+    ///
+    /// let stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Normal);
     ///
-    /// let stats = RollingStats::new(3, Endianness::Big);
+    /// // A capacity other than the default 10 is named explicitly via the const generic.
+    /// let wide: RollingStats<20> =
+    ///     RollingStats::new(15, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Normal);
     /// ```
-    pub fn new(window_size: usize, endianness: Endianness) -> Self {
+    pub fn new(
+        window_size: usize,
+        endianness: Endianness,
+        sample_width: SampleWidth,
+        encoding: Encoding,
+        distribution: SampleDistribution,
+    ) -> Self {
         assert!(
-            window_size > 0 && window_size <= 10,
-            "Window size must be between 1 and 10"
+            window_size > 0 && window_size <= N,
+            "Window size must be between 1 and {N}"
         );
 
         RollingStats {
             window_size,
             endianness,
-            values: Deque::<_, 10>::new(),
-            remainder: Vec::<_, 4>::new(),
-            sum: 0,
-            sum_of_squares: 0,
+            sample_width,
+            encoding,
+            distribution,
+            values: Deque::<_, N>::new(),
+            remainder: Vec::<_, MAX_LEB128_BYTES>::new(),
+            leb128_resyncing: false,
+            mean: 0.0,
+            m2: 0.0,
         }
     }
 
@@ -70,21 +166,37 @@ impl RollingStats {
     ///
     /// If the window is full, the oldest sample is removed.
     ///
+    /// Updates `mean` and `m2` in place using Welford's online algorithm, which
+    /// avoids the catastrophic cancellation that a naive `sum`/`sum_of_squares`
+    /// formulation suffers from and keeps `m2` from ever going negative.
+    ///
     /// # Arguments
     ///
     /// * `value` - The new sample value to add.
-    fn add_sample(&mut self, value: i32) {
+    fn add_sample(&mut self, value: i64) {
         // Remove the oldest sample if the window is full
         if self.values.len() == self.window_size {
             if let Some(removed) = self.values.pop_front() {
-                self.sum -= removed as i64;
-                self.sum_of_squares -= (removed as i64).pow(2);
+                let n = self.values.len() as f64;
+                if n == 0.0 {
+                    self.mean = 0.0;
+                    self.m2 = 0.0;
+                } else {
+                    let removed = removed as f64;
+                    let mean_old = self.mean;
+                    let delta = removed - mean_old;
+                    self.mean -= delta / n;
+                    self.m2 -= delta * (removed - self.mean);
+                }
             }
         }
-        // Add new sample to the back and update sum/squresum
+        // Add new sample to the back and update the running mean/M2
         let _ = self.values.push_back(value);
-        self.sum += value as i64;
-        self.sum_of_squares += (value as i64).pow(2);
+        let n = self.values.len() as f64;
+        let value = value as f64;
+        let delta = value - self.mean;
+        self.mean += delta / n;
+        self.m2 += delta * (value - self.mean);
     }
 
     /// Returns the mean of the samples in the rolling window.
@@ -92,43 +204,96 @@ impl RollingStats {
     /// # Example
     ///
     /// ```
-    /// use rolling_stats::{RollingStats, Endianness};
+    /// use rolling_stats::{RollingStats, Endianness, SampleWidth, Encoding, SampleDistribution};
     ///
-    /// let mut stats = RollingStats::new(3, Endianness::Big);
+    /// let mut stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Normal);
     /// let bytes = [0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3];
     /// stats.write(&bytes);
     /// assert_eq!(stats.mean(), 2.0);
     /// ```
     pub fn mean(&self) -> f32 {
-        let count = self.values.len();
-        // In case of no data, mean is 0
-        if count == 0 {
-            return 0.0;
-        }
-        self.sum as f32 / count as f32
+        self.mean as f32
     }
 
-    /// Returns a random sample from a normal distribution based on the current rolling statistics.
+    /// Returns a random sample from the configured `distribution` based on the current
+    /// rolling statistics, drawn from `rand::thread_rng()`.
+    ///
+    /// This is a thin wrapper around [`sample_with`](Self::sample_with) for callers who have
+    /// access to `std` and don't need a specific, seedable RNG. `no_std` users should call
+    /// [`sample_with`](Self::sample_with) directly with their own [`RngCore`] implementation.
     ///
     /// # Example
     ///
     /// ```
-    /// use rolling_stats::{RollingStats, Endianness};
+    /// use rolling_stats::{RollingStats, Endianness, SampleWidth, Encoding, SampleDistribution};
     ///
-    /// let mut stats = RollingStats::new(3, Endianness::Big);
+    /// let mut stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Normal);
     /// let bytes = [0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3];
     /// stats.write(&bytes);
     /// let sample = stats.sample();
     /// ```
+    #[cfg(feature = "std")]
     pub fn sample(&self) -> f32 {
+        self.sample_with(&mut rand::thread_rng())
+    }
+
+    /// Returns a random sample from the configured `distribution` based on the current
+    /// rolling statistics, drawn through the caller-supplied RNG.
+    ///
+    /// Accepting any [`RngCore`] lets `no_std` users drive sampling from a deterministic,
+    /// explicitly seeded PRNG (e.g. `Pcg32::seed_from_u64`), and lets tests assert exact
+    /// sequences instead of depending on `rand::thread_rng()`.
+    ///
+    /// Falls back to `mean` when the distribution's defining parameter is degenerate: zero
+    /// `std_dev` for [`SampleDistribution::Normal`]/[`SampleDistribution::Cauchy`], or a
+    /// non-positive `mean` (lambda) for [`SampleDistribution::Poisson`].
+    ///
+    /// # Arguments
+    ///
+    /// * `rng` - The random number generator to draw the sample from.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand_pcg::Pcg32;
+    /// use rand_core::SeedableRng;
+    /// use rolling_stats::{RollingStats, Endianness, SampleWidth, Encoding, SampleDistribution};
+    ///
+    /// let mut stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Normal);
+    /// let bytes = [0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3];
+    /// stats.write(&bytes);
+    /// let mut rng = Pcg32::seed_from_u64(42);
+    /// let sample = stats.sample_with(&mut rng);
+    /// ```
+    pub fn sample_with<R: RngCore>(&self, rng: &mut R) -> f32 {
         let mean = self.mean();
         let std_dev = self.std_dev();
-        if std_dev == 0.0 {
-            return mean;
+        match self.distribution {
+            SampleDistribution::Normal => {
+                if std_dev == 0.0 || !std_dev.is_finite() {
+                    return mean;
+                }
+                // Can not fail: std_dev is non-negative and finite, checked above
+                let normal = Normal::new(mean, std_dev).unwrap();
+                normal.sample(rng)
+            }
+            SampleDistribution::Cauchy => {
+                if std_dev == 0.0 || !std_dev.is_finite() {
+                    return mean;
+                }
+                // Can not fail: std_dev is non-negative and finite, checked above
+                let cauchy = Cauchy::new(mean, std_dev).unwrap();
+                cauchy.sample(rng)
+            }
+            SampleDistribution::Poisson => {
+                if mean <= 0.0 || !mean.is_finite() {
+                    return mean;
+                }
+                // Can not fail: mean is strictly positive and finite, checked above
+                let poisson = Poisson::new(mean as f64).unwrap();
+                poisson.sample(rng) as f32
+            }
         }
-        // Can not fail, because std_dev is always non-negative
-        let normal = Normal::new(mean, std_dev).unwrap();
-        normal.sample(&mut rand::thread_rng())
     }
 
     /// Returns the standard deviation of the samples in the rolling window.
@@ -136,9 +301,9 @@ impl RollingStats {
     /// # Example
     ///
     /// ```
-    /// use rolling_stats::{RollingStats, Endianness};
+    /// use rolling_stats::{RollingStats, Endianness, SampleWidth, Encoding, SampleDistribution};
     ///
-    /// let mut stats = RollingStats::new(3, Endianness::Big);
+    /// let mut stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Normal);
     /// let bytes = [0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3];
     /// stats.write(&bytes);
     /// let std_dev = stats.std_dev();
@@ -149,34 +314,48 @@ impl RollingStats {
         if count < 2 {
             return 0.0;
         }
-        let mean = self.mean();
-        let variance = (self.sum_of_squares as f32 / count as f32) - (mean * mean);
-        variance.sqrt()
+        // `m2` is a sum of squared deviations and should never go negative, but `f64`
+        // rounding in the reverse (removal) update can drift it slightly below zero;
+        // clamp so the sqrt below never sees a negative input and returns NaN.
+        let variance = (self.m2 / count as f64).max(0.0);
+        variance.sqrt() as f32
     }
 
-    /// Reads an `i32` value from a byte slice according to the configured endianness.
+    /// Reads a signed integer value from a byte slice according to the configured endianness
+    /// and sample width.
     ///
     /// # Arguments
     ///
-    /// * `bytes` - A byte slice containing the `i32` value.
-    fn read_i32_from_bytes(&self, bytes: &[u8]) -> i32 {
-        match self.endianness {
-            Endianness::Little => LittleEndian::read_i32(&bytes[0..4]),
-            Endianness::Big => BigEndian::read_i32(&bytes[0..4]),
+    /// * `bytes` - A byte slice containing a value of the configured `sample_width`.
+    fn read_sample_from_bytes(&self, bytes: &[u8]) -> i64 {
+        let width = self.sample_width.byte_len();
+        match (self.sample_width, &self.endianness) {
+            (SampleWidth::One, _) => bytes[0] as i8 as i64,
+            (SampleWidth::Two, Endianness::Little) => {
+                LittleEndian::read_i16(&bytes[0..width]) as i64
+            }
+            (SampleWidth::Two, Endianness::Big) => BigEndian::read_i16(&bytes[0..width]) as i64,
+            (SampleWidth::Four, Endianness::Little) => {
+                LittleEndian::read_i32(&bytes[0..width]) as i64
+            }
+            (SampleWidth::Four, Endianness::Big) => BigEndian::read_i32(&bytes[0..width]) as i64,
+            (SampleWidth::Eight, Endianness::Little) => LittleEndian::read_i64(&bytes[0..width]),
+            (SampleWidth::Eight, Endianness::Big) => BigEndian::read_i64(&bytes[0..width]),
         }
     }
 
-    /// Writes the given bytes to the rolling window, interpreting them as `i32` values.
+    /// Writes the given bytes to the rolling window, decoding them according to the
+    /// configured `encoding`.
     ///
-    /// If the byte array does not contain a complete number of `i32` values, the remaining
+    /// If the byte array does not contain a complete number of samples, the remaining
     /// bytes are stored and used in the next call to `write`.
     ///
     /// # Examples
     ///
     /// ```
-    /// use rolling_stats::{RollingStats, Endianness};
+    /// use rolling_stats::{RollingStats, Endianness, SampleWidth, Encoding, SampleDistribution};
     ///
-    /// let mut stats = RollingStats::new(3, Endianness::Big);
+    /// let mut stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Normal);
     /// let bytes = [0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3];
     /// let consumed = stats.write(&bytes);
     /// assert_eq!(consumed, 12);
@@ -186,9 +365,9 @@ impl RollingStats {
     /// Handling incomplete numbers:
     ///
     /// ```
-    /// use rolling_stats::{RollingStats, Endianness};
+    /// use rolling_stats::{RollingStats, Endianness, SampleWidth, Encoding, SampleDistribution};
     ///
-    /// let mut stats = RollingStats::new(3, Endianness::Big);
+    /// let mut stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Normal);
     /// let bytes1 = [0, 0, 0, 1, 0, 0];
     /// let consumed1 = stats.write(&bytes1);
     /// assert_eq!(consumed1, 6);
@@ -200,13 +379,23 @@ impl RollingStats {
     /// assert_eq!(stats.mean(), 2.0);
     /// ```
     pub fn write(&mut self, buf: &[u8]) -> usize {
+        match self.encoding {
+            Encoding::FixedWidth => self.write_fixed_width(buf),
+            Encoding::Leb128 => self.write_leb128(buf),
+        }
+    }
+
+    /// Writes the given bytes, interpreting them as fixed-width samples according to the
+    /// configured `sample_width`.
+    fn write_fixed_width(&mut self, buf: &[u8]) -> usize {
+        let width = self.sample_width.byte_len();
         let mut bytes_consumed = 0;
         let mut start = 0;
         if !self.remainder.is_empty() {
-            start = 4 - self.remainder.len();
+            start = width - self.remainder.len();
             if buf.len() >= start {
                 let _ = self.remainder.extend_from_slice(&buf[0..start]);
-                let value = self.read_i32_from_bytes(&self.remainder);
+                let value = self.read_sample_from_bytes(&self.remainder);
                 self.add_sample(value);
                 self.remainder.clear();
                 bytes_consumed += start;
@@ -215,11 +404,11 @@ impl RollingStats {
                 return buf.len();
             }
         }
-        for chunk in buf[start..].chunks(4) {
-            if chunk.len() == 4 {
-                let value = self.read_i32_from_bytes(chunk);
+        for chunk in buf[start..].chunks(width) {
+            if chunk.len() == width {
+                let value = self.read_sample_from_bytes(chunk);
                 self.add_sample(value);
-                bytes_consumed += 4;
+                bytes_consumed += width;
             } else {
                 let _ = self.remainder.extend_from_slice(chunk);
                 bytes_consumed += chunk.len();
@@ -227,12 +416,150 @@ impl RollingStats {
         }
         bytes_consumed
     }
+
+    /// Writes the given bytes, decoding each sample as a signed LEB128 variable-length
+    /// integer. A partially received varint is stashed in `remainder` across calls; a
+    /// sequence longer than `MAX_LEB128_BYTES` is treated as malformed and dropped, along
+    /// with the rest of its bytes up to and including its terminator, so a corrupt stream
+    /// resynchronizes on the next real value.
+    fn write_leb128(&mut self, buf: &[u8]) -> usize {
+        for &byte in buf {
+            self.leb128_push_byte(byte);
+        }
+        buf.len()
+    }
+
+    /// Feeds a single byte into the in-progress LEB128 sequence in `remainder`, decoding and
+    /// adding a sample when a terminator (high bit clear) completes a well-formed sequence,
+    /// and entering/continuing `leb128_resyncing` when a sequence overflows `remainder`.
+    fn leb128_push_byte(&mut self, byte: u8) {
+        if self.leb128_resyncing {
+            if byte & 0x80 == 0 {
+                self.leb128_resyncing = false;
+            }
+            return;
+        }
+        if self.remainder.push(byte).is_err() {
+            self.remainder.clear();
+            self.leb128_resyncing = byte & 0x80 != 0;
+            return;
+        }
+        if byte & 0x80 == 0 {
+            let value = Self::decode_sleb128(&self.remainder);
+            self.add_sample(value);
+            self.remainder.clear();
+        }
+    }
+
+    /// Decodes a complete signed LEB128 byte sequence (continuation bytes followed by a
+    /// terminator byte with the high bit clear) into an `i64`.
+    fn decode_sleb128(bytes: &[u8]) -> i64 {
+        let mut result: i64 = 0;
+        let mut shift = 0;
+        let mut last_byte = 0u8;
+        for &byte in bytes {
+            last_byte = byte;
+            result |= ((byte & 0x7f) as i64) << shift;
+            shift += 7;
+        }
+        if shift < 64 && (last_byte & 0x40) != 0 {
+            result |= -1i64 << shift;
+        }
+        result
+    }
+
+    /// Writes samples pulled from an arbitrary [`bytes::Buf`], decoding them according to the
+    /// configured `encoding`.
+    ///
+    /// Unlike [`write`](Self::write), the source doesn't need to be a contiguous `&[u8]` first
+    /// — a chained or segmented `Buf` (e.g. reassembled network packets) is consumed directly,
+    /// advancing its cursor as samples are decoded and stashing any trailing partial sample in
+    /// `remainder` exactly as `write` does.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The buffer to read samples from.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rolling_stats::{RollingStats, Endianness, SampleWidth, Encoding, SampleDistribution};
+    ///
+    /// let mut stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Normal);
+    /// let mut buf = &[0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3][..];
+    /// let consumed = stats.write_buf(&mut buf);
+    /// assert_eq!(consumed, 12);
+    /// assert_eq!(stats.mean(), 2.0);
+    /// ```
+    pub fn write_buf<B: Buf>(&mut self, buf: &mut B) -> usize {
+        match self.encoding {
+            Encoding::FixedWidth => self.write_buf_fixed_width(buf),
+            Encoding::Leb128 => self.write_buf_leb128(buf),
+        }
+    }
+
+    /// Pulls fixed-width words out of `buf` according to the configured `sample_width` and
+    /// `endianness`.
+    fn write_buf_fixed_width<B: Buf>(&mut self, buf: &mut B) -> usize {
+        let width = self.sample_width.byte_len();
+        let mut bytes_consumed = 0;
+
+        if !self.remainder.is_empty() {
+            let needed = width - self.remainder.len();
+            let available = buf.remaining().min(needed);
+            for _ in 0..available {
+                let _ = self.remainder.push(buf.get_u8());
+            }
+            bytes_consumed += available;
+            if self.remainder.len() == width {
+                let value = self.read_sample_from_bytes(&self.remainder);
+                self.add_sample(value);
+                self.remainder.clear();
+            } else {
+                return bytes_consumed;
+            }
+        }
+
+        while buf.remaining() >= width {
+            let value = match (self.sample_width, &self.endianness) {
+                (SampleWidth::One, _) => buf.get_i8() as i64,
+                (SampleWidth::Two, Endianness::Little) => buf.get_i16_le() as i64,
+                (SampleWidth::Two, Endianness::Big) => buf.get_i16() as i64,
+                (SampleWidth::Four, Endianness::Little) => buf.get_i32_le() as i64,
+                (SampleWidth::Four, Endianness::Big) => buf.get_i32() as i64,
+                (SampleWidth::Eight, Endianness::Little) => buf.get_i64_le(),
+                (SampleWidth::Eight, Endianness::Big) => buf.get_i64(),
+            };
+            self.add_sample(value);
+            bytes_consumed += width;
+        }
+
+        let tail = buf.remaining();
+        for _ in 0..tail {
+            let _ = self.remainder.push(buf.get_u8());
+        }
+        bytes_consumed + tail
+    }
+
+    /// Pulls LEB128 varints out of `buf`, one byte at a time, with the same malformed-sequence
+    /// handling as [`write_leb128`](Self::write_leb128).
+    fn write_buf_leb128<B: Buf>(&mut self, buf: &mut B) -> usize {
+        let mut bytes_consumed = 0;
+        while buf.has_remaining() {
+            let byte = buf.get_u8();
+            bytes_consumed += 1;
+            self.leb128_push_byte(byte);
+        }
+        bytes_consumed
+    }
 }
 
-impl Default for RollingStats {
+impl Default for RollingStats<10> {
     /// Returns a `RollingStats` instance with default parameters.
     ///
-    /// Default window size is 3, and default endianness is big-endian.
+    /// Default window size is 3, default capacity is 10, default endianness is big-endian,
+    /// default sample width is 4 bytes (`i32`), the default encoding is fixed-width, and the
+    /// default sampling distribution is normal.
     ///
     /// # Example
     ///
@@ -242,13 +569,21 @@ impl Default for RollingStats {
     /// let stats = RollingStats::default();
     /// ```
     fn default() -> Self {
-        RollingStats::new(3, Endianness::default())
+        RollingStats::new(
+            3,
+            Endianness::default(),
+            SampleWidth::default(),
+            Encoding::default(),
+            SampleDistribution::default(),
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand_core::SeedableRng;
+    use rand_pcg::Pcg32;
 
     #[test]
     fn test_default_params() {
@@ -264,7 +599,7 @@ mod tests {
 
     #[test]
     fn test_win_size() {
-        let mut stats = RollingStats::new(4, Endianness::Little);
+        let mut stats: RollingStats = RollingStats::new(4, Endianness::Little, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Normal);
         stats.add_sample(1);
         stats.add_sample(2);
         stats.add_sample(3);
@@ -275,18 +610,50 @@ mod tests {
     }
 
     #[test]
-    fn test_sums_and_squaresums() {
-        let mut stats = RollingStats::new(3, Endianness::Big);
+    fn test_capacity_beyond_default() {
+        let mut stats: RollingStats<20> = RollingStats::new(
+            20,
+            Endianness::Big,
+            SampleWidth::Four,
+            Encoding::FixedWidth,
+            SampleDistribution::Normal,
+        );
+        for sample in 1..=20 {
+            stats.add_sample(sample);
+        }
+        assert_eq!(stats.values.len(), 20);
+        assert_eq!(stats.mean(), 10.5);
+
+        stats.add_sample(21);
+        assert_eq!(stats.values.len(), 20);
+        assert_eq!(stats.mean(), 11.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "Window size must be between 1 and 5")]
+    fn test_window_size_exceeds_capacity_panics() {
+        let _stats: RollingStats<5> = RollingStats::new(
+            6,
+            Endianness::Big,
+            SampleWidth::Four,
+            Encoding::FixedWidth,
+            SampleDistribution::Normal,
+        );
+    }
+
+    #[test]
+    fn test_mean_and_m2() {
+        let mut stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Normal);
         stats.add_sample(1);
         stats.add_sample(2);
         stats.add_sample(3);
-        assert_eq!(stats.sum, 6);
-        assert_eq!(stats.sum_of_squares, 14);
+        assert_eq!(stats.mean, 2.0);
+        assert_eq!(stats.m2, 2.0);
     }
 
     #[test]
     fn test_mean() {
-        let mut stats = RollingStats::new(3, Endianness::Big);
+        let mut stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Normal);
         stats.add_sample(1);
         stats.add_sample(2);
         stats.add_sample(3);
@@ -297,7 +664,7 @@ mod tests {
 
     #[test]
     fn test_std_dev() {
-        let mut stats = RollingStats::new(3, Endianness::Big);
+        let mut stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Normal);
         stats.add_sample(1);
         stats.add_sample(2);
         stats.add_sample(3);
@@ -312,8 +679,27 @@ mod tests {
     }
 
     #[test]
+    fn test_std_dev_large_magnitude_i64() {
+        // Regression test for f32 mean/m2 accumulators collapsing precision at realistic
+        // large-magnitude scales (e.g. epoch-millisecond timestamps or counters): four
+        // consecutive i64 values base..base+3 have a true std_dev of ~1.1180 regardless of
+        // base, but an f32 accumulator returns a visibly different value at base = 1e7 and
+        // silently 0 at base >= 1e8. f64 keeps these values exact (well under 2^53) and
+        // preserves the result.
+        let mut stats: RollingStats = RollingStats::new(4, Endianness::Big, SampleWidth::Eight, Encoding::FixedWidth, SampleDistribution::Normal);
+        let base: i64 = 1_700_000_000_000;
+        for offset in 0..4 {
+            stats.add_sample(base + offset);
+        }
+        let std_dev = stats.std_dev();
+
+        assert!((std_dev - 1.1180).abs() < 0.0001);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
     fn test_sample_one_sample() {
-        let mut stats = RollingStats::new(3, Endianness::Big);
+        let mut stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Normal);
         stats.add_sample(1);
         let value = stats.sample();
 
@@ -321,51 +707,142 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "std")]
     fn test_sample_zero_samples() {
-        let stats = RollingStats::new(3, Endianness::Big);
+        let stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Normal);
         let value = stats.sample();
 
         assert!((value - 0.0).abs() < 0.000001);
     }
 
+    #[test]
+    fn test_sample_with_is_deterministic() {
+        let mut stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Normal);
+        stats.add_sample(1);
+        stats.add_sample(2);
+        stats.add_sample(3);
+
+        let mut rng1 = Pcg32::seed_from_u64(42);
+        let mut rng2 = Pcg32::seed_from_u64(42);
+        assert_eq!(stats.sample_with(&mut rng1), stats.sample_with(&mut rng2));
+    }
+
+    #[test]
+    fn test_sample_with_zero_samples() {
+        let stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Normal);
+        let mut rng = Pcg32::seed_from_u64(42);
+        let value = stats.sample_with(&mut rng);
+
+        assert!((value - 0.0).abs() < 0.000001);
+    }
+
+    #[test]
+    fn test_sample_with_cauchy_is_deterministic() {
+        let mut stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Cauchy);
+        stats.add_sample(1);
+        stats.add_sample(2);
+        stats.add_sample(3);
+
+        let mut rng1 = Pcg32::seed_from_u64(42);
+        let mut rng2 = Pcg32::seed_from_u64(42);
+        assert_eq!(stats.sample_with(&mut rng1), stats.sample_with(&mut rng2));
+    }
+
+    #[test]
+    fn test_sample_with_cauchy_zero_samples() {
+        // `std_dev` is 0 with fewer than 2 samples, so Cauchy falls back to `mean`.
+        let stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Cauchy);
+        let mut rng = Pcg32::seed_from_u64(42);
+        let value = stats.sample_with(&mut rng);
+
+        assert!((value - 0.0).abs() < 0.000001);
+    }
+
+    #[test]
+    fn test_sample_with_poisson_is_deterministic() {
+        let mut stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Poisson);
+        stats.add_sample(1);
+        stats.add_sample(2);
+        stats.add_sample(3);
+
+        let mut rng1 = Pcg32::seed_from_u64(42);
+        let mut rng2 = Pcg32::seed_from_u64(42);
+        assert_eq!(stats.sample_with(&mut rng1), stats.sample_with(&mut rng2));
+    }
+
+    #[test]
+    fn test_sample_with_poisson_non_positive_mean() {
+        // `mean` is 0 with zero samples, so Poisson falls back to `mean` instead of
+        // calling `Poisson::new` with a non-positive lambda.
+        let stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Poisson);
+        let mut rng = Pcg32::seed_from_u64(42);
+        let value = stats.sample_with(&mut rng);
+
+        assert!((value - 0.0).abs() < 0.000001);
+    }
+
     #[test]
     fn test_read_i32_le() {
-        let stats = RollingStats::new(3, Endianness::Little);
+        let stats: RollingStats = RollingStats::new(3, Endianness::Little, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Normal);
         let bytes = [1, 0, 0, 0];
 
-        assert_eq!(stats.read_i32_from_bytes(&bytes), 1);
+        assert_eq!(stats.read_sample_from_bytes(&bytes), 1);
     }
 
     #[test]
     fn test_read_i32_be() {
-        let stats = RollingStats::new(3, Endianness::Big);
+        let stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Normal);
         let bytes = [0, 0, 0, 1];
 
-        assert_eq!(stats.read_i32_from_bytes(&bytes), 1);
+        assert_eq!(stats.read_sample_from_bytes(&bytes), 1);
+    }
+
+    #[test]
+    fn test_read_i8() {
+        let stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::One, Encoding::FixedWidth, SampleDistribution::Normal);
+        let bytes = [0xFFu8];
+
+        assert_eq!(stats.read_sample_from_bytes(&bytes), -1);
+    }
+
+    #[test]
+    fn test_read_i16_le() {
+        let stats: RollingStats = RollingStats::new(3, Endianness::Little, SampleWidth::Two, Encoding::FixedWidth, SampleDistribution::Normal);
+        let bytes = [1, 0];
+
+        assert_eq!(stats.read_sample_from_bytes(&bytes), 1);
+    }
+
+    #[test]
+    fn test_read_i64_be() {
+        let stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Eight, Encoding::FixedWidth, SampleDistribution::Normal);
+        let bytes = [0, 0, 0, 0, 0, 0, 0, 1];
+
+        assert_eq!(stats.read_sample_from_bytes(&bytes), 1);
     }
 
     #[test]
     fn test_write_no_rem() {
-        let mut stats = RollingStats::new(3, Endianness::Big);
+        let mut stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Normal);
         let bytes = [0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3];
         let ret = stats.write(&bytes);
 
         assert_eq!(ret, 12);
         assert_eq!(stats.values.len(), 3);
-        assert_eq!(stats.sum, 6);
+        assert_eq!(stats.mean(), 2.0);
         assert_eq!(stats.remainder.len(), 0);
     }
 
     #[test]
     fn test_write_with_remainder() {
-        let mut stats = RollingStats::new(4, Endianness::Big);
+        let mut stats: RollingStats = RollingStats::new(4, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Normal);
 
         // Addition of several elements with a remainder
         let bytes = [0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0];
         let ret = stats.write(&bytes);
         assert_eq!(ret, 13);
         assert_eq!(stats.values.len(), 3);
-        assert_eq!(stats.sum, 6);
+        assert_eq!(stats.mean(), 2.0);
         assert_eq!(stats.remainder.len(), 1);
 
         // Only extending a reminder
@@ -373,7 +850,7 @@ mod tests {
         let ret = stats.write(&bytes);
         assert_eq!(ret, 1);
         assert_eq!(stats.values.len(), 3);
-        assert_eq!(stats.sum, 6);
+        assert_eq!(stats.mean(), 2.0);
         assert_eq!(stats.remainder.len(), 2);
 
         // Cancellation of a reminder
@@ -381,7 +858,164 @@ mod tests {
         let ret = stats.write(&bytes);
         assert_eq!(ret, 2);
         assert_eq!(stats.values.len(), 4);
-        assert_eq!(stats.sum, 10);
+        assert_eq!(stats.mean(), 2.5);
+        assert_eq!(stats.remainder.len(), 0);
+    }
+
+    #[test]
+    fn test_write_two_byte_width() {
+        let mut stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Two, Encoding::FixedWidth, SampleDistribution::Normal);
+        let bytes = [0, 1, 0, 2, 0, 3];
+        let ret = stats.write(&bytes);
+
+        assert_eq!(ret, 6);
+        assert_eq!(stats.values.len(), 3);
+        assert_eq!(stats.mean(), 2.0);
+        assert_eq!(stats.remainder.len(), 0);
+    }
+
+    #[test]
+    fn test_write_one_byte_width() {
+        let mut stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::One, Encoding::FixedWidth, SampleDistribution::Normal);
+        let bytes = [1, 2, 3];
+        let ret = stats.write(&bytes);
+
+        assert_eq!(ret, 3);
+        assert_eq!(stats.values.len(), 3);
+        assert_eq!(stats.mean(), 2.0);
+        assert_eq!(stats.remainder.len(), 0);
+    }
+
+    #[test]
+    fn test_write_leb128_single_byte_values() {
+        let mut stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::Leb128, SampleDistribution::Normal);
+        // 1, 2, 3 each fit in a single LEB128 byte.
+        let bytes = [1, 2, 3];
+        let ret = stats.write(&bytes);
+
+        assert_eq!(ret, 3);
+        assert_eq!(stats.values.len(), 3);
+        assert_eq!(stats.mean(), 2.0);
+        assert_eq!(stats.remainder.len(), 0);
+    }
+
+    #[test]
+    fn test_write_leb128_negative_value() {
+        let mut stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::Leb128, SampleDistribution::Normal);
+        // 0x7e is the single-byte SLEB128 encoding of -2.
+        let ret = stats.write(&[0x7e]);
+
+        assert_eq!(ret, 1);
+        assert_eq!(stats.values.len(), 1);
+        assert_eq!(stats.mean(), -2.0);
+    }
+
+    #[test]
+    fn test_write_leb128_multi_byte_value_with_remainder() {
+        let mut stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::Leb128, SampleDistribution::Normal);
+        // 0xAC, 0x02 is the two-byte SLEB128 encoding of 300, split across two writes.
+        let ret1 = stats.write(&[0xAC]);
+        assert_eq!(ret1, 1);
+        assert_eq!(stats.values.len(), 0);
+        assert_eq!(stats.remainder.len(), 1);
+
+        let ret2 = stats.write(&[0x02]);
+        assert_eq!(ret2, 1);
+        assert_eq!(stats.values.len(), 1);
+        assert_eq!(stats.mean(), 300.0);
+        assert_eq!(stats.remainder.len(), 0);
+    }
+
+    #[test]
+    fn test_write_leb128_overlong_sequence_is_dropped() {
+        let mut stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::Leb128, SampleDistribution::Normal);
+        // MAX_LEB128_BYTES + 1 continuation bytes overflow the remainder buffer; the whole
+        // malformed run, including its eventual terminator, is discarded without decoding so
+        // the stream resynchronizes on the next real value.
+        let mut bytes = Vec::<u8, 16>::new();
+        for _ in 0..(MAX_LEB128_BYTES + 1) {
+            let _ = bytes.push(0xFF);
+        }
+        let _ = bytes.push(0x00); // terminator of the garbage run; discarded, not decoded
+        let _ = bytes.push(2); // a fresh, well-formed value
+        let ret = stats.write(&bytes);
+
+        assert_eq!(ret, bytes.len());
+        assert_eq!(stats.values.len(), 1);
+        assert_eq!(stats.mean(), 2.0);
+    }
+
+    #[test]
+    fn test_write_leb128_overflow_without_immediate_terminator_does_not_resync_early() {
+        let mut stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::Leb128, SampleDistribution::Normal);
+        // The overflowing byte (#11) and the byte after it are both still continuation bytes
+        // of the same malformed run; without tracking `leb128_resyncing`, the second one would
+        // wrongly start a fresh (garbage) varint the moment `remainder` is cleared.
+        let mut bytes = Vec::<u8, 16>::new();
+        for _ in 0..(MAX_LEB128_BYTES + 2) {
+            let _ = bytes.push(0xFF);
+        }
+        let _ = bytes.push(0x00); // terminator of the garbage run; discarded, not decoded
+        let _ = bytes.push(3); // a fresh, well-formed value
+        let ret = stats.write(&bytes);
+
+        assert_eq!(ret, bytes.len());
+        assert_eq!(stats.values.len(), 1);
+        assert_eq!(stats.mean(), 3.0);
+    }
+
+    #[test]
+    fn test_write_buf_no_rem() {
+        let mut stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Normal);
+        let mut buf = &[0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3][..];
+        let ret = stats.write_buf(&mut buf);
+
+        assert_eq!(ret, 12);
+        assert_eq!(stats.values.len(), 3);
+        assert_eq!(stats.mean(), 2.0);
+        assert_eq!(stats.remainder.len(), 0);
+        assert_eq!(buf.remaining(), 0);
+    }
+
+    #[test]
+    fn test_write_buf_with_remainder() {
+        let mut stats: RollingStats = RollingStats::new(4, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Normal);
+
+        let mut buf = &[0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0][..];
+        let ret = stats.write_buf(&mut buf);
+        assert_eq!(ret, 13);
+        assert_eq!(stats.values.len(), 3);
+        assert_eq!(stats.mean(), 2.0);
+        assert_eq!(stats.remainder.len(), 1);
+
+        let mut buf = &[0, 0, 4][..];
+        let ret = stats.write_buf(&mut buf);
+        assert_eq!(ret, 3);
+        assert_eq!(stats.values.len(), 4);
+        assert_eq!(stats.mean(), 2.5);
         assert_eq!(stats.remainder.len(), 0);
     }
+
+    #[test]
+    fn test_write_buf_leb128() {
+        let mut stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::Leb128, SampleDistribution::Normal);
+        // 0xAC, 0x02 is the two-byte SLEB128 encoding of 300.
+        let mut buf = &[0xAC, 0x02, 1][..];
+        let ret = stats.write_buf(&mut buf);
+
+        assert_eq!(ret, 3);
+        assert_eq!(stats.values.len(), 2);
+        assert_eq!(stats.mean(), 150.5);
+    }
+
+    #[test]
+    fn test_write_buf_chained_segments() {
+        let mut stats: RollingStats = RollingStats::new(3, Endianness::Big, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Normal);
+        let mut buf = (&[0, 0, 0, 1, 0][..]).chain(&[0, 0, 2][..]);
+        let ret = stats.write_buf(&mut buf);
+
+        assert_eq!(ret, 8);
+        assert_eq!(stats.values.len(), 2);
+        assert_eq!(stats.mean(), 1.5);
+    }
 }
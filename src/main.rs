@@ -1,19 +1,21 @@
 #![no_std]
 
-use rolling_stats::{RollingStats,Endianness};
+use rolling_stats::{RollingStats,Endianness,SampleWidth,Encoding,SampleDistribution};
 use libc_print::std_name::println;
 
 fn main() {
     println!("Creating empty default stats");
     let mut stats: RollingStats = RollingStats::default();
+    #[cfg(feature = "std")]
     println!("Random sample (0 expected) {}", stats.sample());
     assert_eq!(stats.write(&[0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4]), 16);
     assert_eq!(stats.mean(), 3.0);
     println!("{:?}", stats);
+    #[cfg(feature = "std")]
     println!("Random sample {}", stats.sample());
 
     println!("Creating little endian stats with size 4");
-    let mut stats: RollingStats = RollingStats::new(4, Endianness::Little);
+    let mut stats: RollingStats = RollingStats::new(4, Endianness::Little, SampleWidth::Four, Encoding::FixedWidth, SampleDistribution::Normal);
     assert_eq!(stats.write(&[1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0]), 16);
     assert_eq!(stats.mean(), 2.5);
     println!("{:?}", stats);